@@ -1,12 +1,11 @@
 use std::collections::HashSet;
-use std::ffi::OsString;
 use std::io::Read;
 use std::path::PathBuf;
 
 use anyhow::{Context, Result, bail};
 use clap::builder::TypedValueParser;
 use clap::{Parser, ValueEnum, ValueHint};
-use image::ExtendedColorType;
+use image::{ExtendedColorType, ImageEncoder};
 use rayon::prelude::*;
 use tiny_skia::Pixmap;
 
@@ -14,6 +13,7 @@ use tiny_skia::Pixmap;
 #[derive(Clone, Debug, PartialEq, Eq, Hash, ValueEnum)]
 enum Format {
     Avif,
+    Exr,
     Jpeg,
     Png,
     Tiff,
@@ -24,6 +24,7 @@ impl Format {
     fn extension(&self) -> String {
         match self {
             Self::Avif => String::from("avif"),
+            Self::Exr => String::from("exr"),
             Self::Jpeg => String::from("jpeg"),
             Self::Png => String::from("png"),
             Self::Tiff => String::from("tiff"),
@@ -33,19 +34,37 @@ impl Format {
 
     fn has_alpha_channel(&self) -> bool {
         match self {
-            Format::Avif | Format::Png | Format::Tiff | Format::Webp => true,
+            Format::Avif | Format::Exr | Format::Png | Format::Tiff | Format::Webp => true,
             Format::Jpeg => false,
         }
     }
 }
 
-/// An input that is either stdin or a real path.
+/// TIFF compression scheme, forwarded to the `tiff` crate's encoder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum TiffCompression {
+    None,
+    Lzw,
+    Deflate,
+    Packbits,
+}
+
+/// An input that is either stdin, a single file, a directory, or a glob pattern to batch over.
 #[derive(Debug, Clone)]
 enum Input {
     /// Stdin, represented by `-`.
     Stdin,
-    /// A non-empty path.
+    /// A non-empty path to a single file.
     Path(PathBuf),
+    /// A non-empty path to a directory, batch-processed recursively.
+    Dir(PathBuf),
+    /// A glob pattern (e.g. `icons/**/*.svg`), batch-processed over every match.
+    Glob(String),
+}
+
+/// Whether `s` contains a glob metacharacter, i.e. isn't a plain path.
+fn is_glob_pattern(s: &str) -> bool {
+    s.contains(['*', '?', '[', '{'])
 }
 
 fn input_value_parser() -> impl TypedValueParser<Value = Input> {
@@ -54,9 +73,73 @@ fn input_value_parser() -> impl TypedValueParser<Value = Input> {
             Err(clap::Error::new(clap::error::ErrorKind::InvalidValue))
         } else if v == "-" {
             Ok(Input::Stdin)
+        } else if let Some(s) = v.to_str().filter(|s| is_glob_pattern(s)) {
+            Ok(Input::Glob(s.to_string()))
         } else {
-            Ok(Input::Path(v.into()))
+            let path = PathBuf::from(&v);
+            if path.is_dir() {
+                Ok(Input::Dir(path))
+            } else {
+                Ok(Input::Path(path))
+            }
+        }
+    })
+}
+
+/// A physical unit accepted as a `--width`/`--height` suffix, convertible to inches.
+#[derive(Debug, Clone, Copy)]
+enum LengthUnit {
+    Mm,
+    In,
+    Pt,
+}
+
+impl LengthUnit {
+    fn to_inches(self, value: f32) -> f32 {
+        match self {
+            Self::Mm => value / 25.4,
+            Self::In => value,
+            Self::Pt => value / 72.0,
+        }
+    }
+}
+
+/// A parsed `--width`/`--height` value: either a bare pixel count, or a physical length
+/// (e.g. `50mm`) that gets converted to pixels using `--dpi`.
+#[derive(Debug, Clone, Copy)]
+enum SizeArg {
+    Px(u32),
+    Unit(f32, LengthUnit),
+}
+
+impl SizeArg {
+    fn to_pixels(self, dpi: f32) -> f32 {
+        match self {
+            Self::Px(v) => v as f32,
+            Self::Unit(v, unit) => unit.to_inches(v) * dpi,
         }
+    }
+}
+
+fn size_value_parser() -> impl TypedValueParser<Value = SizeArg> {
+    clap::builder::StringValueParser::new().try_map(|v| {
+        if let Ok(px) = v.parse::<u32>() {
+            return Ok(SizeArg::Px(px));
+        }
+
+        let (num, unit) = if let Some(n) = v.strip_suffix("mm") {
+            (n, LengthUnit::Mm)
+        } else if let Some(n) = v.strip_suffix("pt") {
+            (n, LengthUnit::Pt)
+        } else if let Some(n) = v.strip_suffix("in") {
+            (n, LengthUnit::In)
+        } else {
+            return Err(clap::Error::new(clap::error::ErrorKind::InvalidValue));
+        };
+
+        num.parse::<f32>()
+            .map(|v| SizeArg::Unit(v, unit))
+            .map_err(|_| clap::Error::new(clap::error::ErrorKind::InvalidValue))
     })
 }
 
@@ -71,7 +154,8 @@ fn read_from_stdin() -> Result<Vec<u8>> {
 #[derive(Clone, Debug, Parser)]
 #[command(version, about, long_about = None)]
 struct Config {
-    /// Path to input SVG file. Use `-` to read input from stdin.
+    /// Path to input SVG file, a directory to batch over recursively, or a glob pattern (e.g.
+    /// `icons/**/*.svg`). Use `-` to read input from stdin.
     #[clap(value_parser = input_value_parser(), value_hint = ValueHint::FilePath)]
     input: Input,
 
@@ -80,7 +164,8 @@ struct Config {
     output: Option<PathBuf>,
 
     /// Custom output filename without an extension. When input is from 'stdin', this option is
-    /// required.
+    /// required. Illegal when input is a directory or a glob pattern, since names are derived
+    /// from each file.
     #[arg(long)]
     filename: Option<String>,
 
@@ -89,22 +174,52 @@ struct Config {
     #[arg(short, long, value_delimiter = ',', value_enum)]
     format: Option<Vec<Format>>,
 
-    /// Output width in pixels (overrides '--scale').
-    #[arg(long)]
-    width: Option<u32>,
+    /// Output width, in pixels or with a physical unit suffix ('mm', 'in', 'pt'), e.g. '500' or
+    /// '50mm' (overrides '--scale'). Unit suffixes are converted to pixels using '--dpi'.
+    #[arg(long, value_parser = size_value_parser())]
+    width: Option<SizeArg>,
 
-    /// Output height in pixels (overrides '--scale').
-    #[arg(long)]
-    height: Option<u32>,
+    /// Output height, in pixels or with a physical unit suffix ('mm', 'in', 'pt'), e.g. '500' or
+    /// '50mm' (overrides '--scale'). Unit suffixes are converted to pixels using '--dpi'.
+    #[arg(long, value_parser = size_value_parser())]
+    height: Option<SizeArg>,
 
     /// Scale factor relative to the SVG's intrinsic size.
     #[arg(long, default_value_t = 1.0)]
     scale: f32,
 
-    /// Background color in hex ('#RRGGBB' or '#RRGGBBAA'). By default, for formats that support alpha channel it will be
+    /// DPI used to resolve physical units, both for unit-suffixed '--width'/'--height' and for
+    /// SVGs whose own dimensions are declared in physical units (e.g. 'mm', 'in', 'pt').
+    #[arg(long, default_value_t = 96.0)]
+    dpi: f32,
+
+    /// Background color: hex ('#RGB', '#RGBA', '#RRGGBB', '#RRGGBBAA'), a named CSS color
+    /// ('rebeccapurple', 'cornflowerblue', 'transparent', ...), 'rgb()'/'rgba()', or
+    /// 'hsl()'/'hsla()'. By default, for formats that support alpha channel it will be
     /// transparent, otherwise it will be filled with solid white.
     #[arg(long)]
     background: Option<String>,
+
+    /// JPEG quality, from 1 (worst) to 100 (best).
+    #[arg(long, default_value_t = 90, value_parser = clap::value_parser!(u8).range(1..=100))]
+    jpeg_quality: u8,
+
+    /// AVIF quality, from 1 (worst) to 100 (best).
+    #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u8).range(1..=100))]
+    avif_quality: u8,
+
+    /// AVIF encoder speed, from 0 (slowest, best compression) to 10 (fastest).
+    #[arg(long, default_value_t = 6, value_parser = clap::value_parser!(u8).range(0..=10))]
+    avif_speed: u8,
+
+    /// TIFF compression scheme.
+    #[arg(long, value_enum, default_value = "lzw")]
+    tiff_compression: TiffCompression,
+
+    /// Convert EXR pixels from sRGB to linear light before writing. By default, EXR keeps the
+    /// unpremultiplied sRGB-encoded (non-linear) channel values.
+    #[arg(long)]
+    exr_linear: bool,
 }
 
 /// Simple file validation if input file exists and has a valid '.svg' extension file.
@@ -119,29 +234,464 @@ fn is_svg_file(path: &PathBuf) -> bool {
     path.is_file() && is_valid_ext
 }
 
+/// The ~148 CSS/SVG named colors accepted by `parse_color`, e.g. `rebeccapurple`.
+const NAMED_COLORS: &[(&str, u8, u8, u8)] = &[
+    ("aliceblue", 0xF0, 0xF8, 0xFF),
+    ("antiquewhite", 0xFA, 0xEB, 0xD7),
+    ("aqua", 0x00, 0xFF, 0xFF),
+    ("aquamarine", 0x7F, 0xFF, 0xD4),
+    ("azure", 0xF0, 0xFF, 0xFF),
+    ("beige", 0xF5, 0xF5, 0xDC),
+    ("bisque", 0xFF, 0xE4, 0xC4),
+    ("black", 0x00, 0x00, 0x00),
+    ("blanchedalmond", 0xFF, 0xEB, 0xCD),
+    ("blue", 0x00, 0x00, 0xFF),
+    ("blueviolet", 0x8A, 0x2B, 0xE2),
+    ("brown", 0xA5, 0x2A, 0x2A),
+    ("burlywood", 0xDE, 0xB8, 0x87),
+    ("cadetblue", 0x5F, 0x9E, 0xA0),
+    ("chartreuse", 0x7F, 0xFF, 0x00),
+    ("chocolate", 0xD2, 0x69, 0x1E),
+    ("coral", 0xFF, 0x7F, 0x50),
+    ("cornflowerblue", 0x64, 0x95, 0xED),
+    ("cornsilk", 0xFF, 0xF8, 0xDC),
+    ("crimson", 0xDC, 0x14, 0x3C),
+    ("cyan", 0x00, 0xFF, 0xFF),
+    ("darkblue", 0x00, 0x00, 0x8B),
+    ("darkcyan", 0x00, 0x8B, 0x8B),
+    ("darkgoldenrod", 0xB8, 0x86, 0x0B),
+    ("darkgray", 0xA9, 0xA9, 0xA9),
+    ("darkgreen", 0x00, 0x64, 0x00),
+    ("darkgrey", 0xA9, 0xA9, 0xA9),
+    ("darkkhaki", 0xBD, 0xB7, 0x6B),
+    ("darkmagenta", 0x8B, 0x00, 0x8B),
+    ("darkolivegreen", 0x55, 0x6B, 0x2F),
+    ("darkorange", 0xFF, 0x8C, 0x00),
+    ("darkorchid", 0x99, 0x32, 0xCC),
+    ("darkred", 0x8B, 0x00, 0x00),
+    ("darksalmon", 0xE9, 0x96, 0x7A),
+    ("darkseagreen", 0x8F, 0xBC, 0x8F),
+    ("darkslateblue", 0x48, 0x3D, 0x8B),
+    ("darkslategray", 0x2F, 0x4F, 0x4F),
+    ("darkslategrey", 0x2F, 0x4F, 0x4F),
+    ("darkturquoise", 0x00, 0xCE, 0xD1),
+    ("darkviolet", 0x94, 0x00, 0xD3),
+    ("deeppink", 0xFF, 0x14, 0x93),
+    ("deepskyblue", 0x00, 0xBF, 0xFF),
+    ("dimgray", 0x69, 0x69, 0x69),
+    ("dimgrey", 0x69, 0x69, 0x69),
+    ("dodgerblue", 0x1E, 0x90, 0xFF),
+    ("firebrick", 0xB2, 0x22, 0x22),
+    ("floralwhite", 0xFF, 0xFA, 0xF0),
+    ("forestgreen", 0x22, 0x8B, 0x22),
+    ("fuchsia", 0xFF, 0x00, 0xFF),
+    ("gainsboro", 0xDC, 0xDC, 0xDC),
+    ("ghostwhite", 0xF8, 0xF8, 0xFF),
+    ("gold", 0xFF, 0xD7, 0x00),
+    ("goldenrod", 0xDA, 0xA5, 0x20),
+    ("gray", 0x80, 0x80, 0x80),
+    ("grey", 0x80, 0x80, 0x80),
+    ("green", 0x00, 0x80, 0x00),
+    ("greenyellow", 0xAD, 0xFF, 0x2F),
+    ("honeydew", 0xF0, 0xFF, 0xF0),
+    ("hotpink", 0xFF, 0x69, 0xB4),
+    ("indianred", 0xCD, 0x5C, 0x5C),
+    ("indigo", 0x4B, 0x00, 0x82),
+    ("ivory", 0xFF, 0xFF, 0xF0),
+    ("khaki", 0xF0, 0xE6, 0x8C),
+    ("lavender", 0xE6, 0xE6, 0xFA),
+    ("lavenderblush", 0xFF, 0xF0, 0xF5),
+    ("lawngreen", 0x7C, 0xFC, 0x00),
+    ("lemonchiffon", 0xFF, 0xFA, 0xCD),
+    ("lightblue", 0xAD, 0xD8, 0xE6),
+    ("lightcoral", 0xF0, 0x80, 0x80),
+    ("lightcyan", 0xE0, 0xFF, 0xFF),
+    ("lightgoldenrodyellow", 0xFA, 0xFA, 0xD2),
+    ("lightgray", 0xD3, 0xD3, 0xD3),
+    ("lightgreen", 0x90, 0xEE, 0x90),
+    ("lightgrey", 0xD3, 0xD3, 0xD3),
+    ("lightpink", 0xFF, 0xB6, 0xC1),
+    ("lightsalmon", 0xFF, 0xA0, 0x7A),
+    ("lightseagreen", 0x20, 0xB2, 0xAA),
+    ("lightskyblue", 0x87, 0xCE, 0xFA),
+    ("lightslategray", 0x77, 0x88, 0x99),
+    ("lightslategrey", 0x77, 0x88, 0x99),
+    ("lightsteelblue", 0xB0, 0xC4, 0xDE),
+    ("lightyellow", 0xFF, 0xFF, 0xE0),
+    ("lime", 0x00, 0xFF, 0x00),
+    ("limegreen", 0x32, 0xCD, 0x32),
+    ("linen", 0xFA, 0xF0, 0xE6),
+    ("magenta", 0xFF, 0x00, 0xFF),
+    ("maroon", 0x80, 0x00, 0x00),
+    ("mediumaquamarine", 0x66, 0xCD, 0xAA),
+    ("mediumblue", 0x00, 0x00, 0xCD),
+    ("mediumorchid", 0xBA, 0x55, 0xD3),
+    ("mediumpurple", 0x93, 0x70, 0xDB),
+    ("mediumseagreen", 0x3C, 0xB3, 0x71),
+    ("mediumslateblue", 0x7B, 0x68, 0xEE),
+    ("mediumspringgreen", 0x00, 0xFA, 0x9A),
+    ("mediumturquoise", 0x48, 0xD1, 0xCC),
+    ("mediumvioletred", 0xC7, 0x15, 0x85),
+    ("midnightblue", 0x19, 0x19, 0x70),
+    ("mintcream", 0xF5, 0xFF, 0xFA),
+    ("mistyrose", 0xFF, 0xE4, 0xE1),
+    ("moccasin", 0xFF, 0xE4, 0xB5),
+    ("navajowhite", 0xFF, 0xDE, 0xAD),
+    ("navy", 0x00, 0x00, 0x80),
+    ("oldlace", 0xFD, 0xF5, 0xE6),
+    ("olive", 0x80, 0x80, 0x00),
+    ("olivedrab", 0x6B, 0x8E, 0x23),
+    ("orange", 0xFF, 0xA5, 0x00),
+    ("orangered", 0xFF, 0x45, 0x00),
+    ("orchid", 0xDA, 0x70, 0xD6),
+    ("palegoldenrod", 0xEE, 0xE8, 0xAA),
+    ("palegreen", 0x98, 0xFB, 0x98),
+    ("paleturquoise", 0xAF, 0xEE, 0xEE),
+    ("palevioletred", 0xDB, 0x70, 0x93),
+    ("papayawhip", 0xFF, 0xEF, 0xD5),
+    ("peachpuff", 0xFF, 0xDA, 0xB9),
+    ("peru", 0xCD, 0x85, 0x3F),
+    ("pink", 0xFF, 0xC0, 0xCB),
+    ("plum", 0xDD, 0xA0, 0xDD),
+    ("powderblue", 0xB0, 0xE0, 0xE6),
+    ("purple", 0x80, 0x00, 0x80),
+    ("rebeccapurple", 0x66, 0x33, 0x99),
+    ("red", 0xFF, 0x00, 0x00),
+    ("rosybrown", 0xBC, 0x8F, 0x8F),
+    ("royalblue", 0x41, 0x69, 0xE1),
+    ("saddlebrown", 0x8B, 0x45, 0x13),
+    ("salmon", 0xFA, 0x80, 0x72),
+    ("sandybrown", 0xF4, 0xA4, 0x60),
+    ("seagreen", 0x2E, 0x8B, 0x57),
+    ("seashell", 0xFF, 0xF5, 0xEE),
+    ("sienna", 0xA0, 0x52, 0x2D),
+    ("silver", 0xC0, 0xC0, 0xC0),
+    ("skyblue", 0x87, 0xCE, 0xEB),
+    ("slateblue", 0x6A, 0x5A, 0xCD),
+    ("slategray", 0x70, 0x80, 0x90),
+    ("slategrey", 0x70, 0x80, 0x90),
+    ("snow", 0xFF, 0xFA, 0xFA),
+    ("springgreen", 0x00, 0xFF, 0x7F),
+    ("steelblue", 0x46, 0x82, 0xB4),
+    ("tan", 0xD2, 0xB4, 0x8C),
+    ("teal", 0x00, 0x80, 0x80),
+    ("thistle", 0xD8, 0xBF, 0xD8),
+    ("tomato", 0xFF, 0x63, 0x47),
+    ("turquoise", 0x40, 0xE0, 0xD0),
+    ("violet", 0xEE, 0x82, 0xEE),
+    ("wheat", 0xF5, 0xDE, 0xB3),
+    ("white", 0xFF, 0xFF, 0xFF),
+    ("whitesmoke", 0xF5, 0xF5, 0xF5),
+    ("yellow", 0xFF, 0xFF, 0x00),
+    ("yellowgreen", 0x9A, 0xCD, 0x32),
+];
+
+/// Expands a single hex nibble into a byte by duplicating it (CSS short-hex rule, e.g. `f` -> `0xff`).
+fn expand_hex_nibble(c: char) -> Result<u8> {
+    let v = c
+        .to_digit(16)
+        .ok_or_else(|| anyhow::anyhow!("Invalid hex digit '{}'", c))? as u8;
+
+    Ok(v * 16 + v)
+}
+
+fn parse_hex_color(s: &str) -> Result<tiny_skia::Color> {
+    let chars: Vec<char> = s.chars().collect();
+
+    let (r, g, b, a) = match chars.len() {
+        3 => (
+            expand_hex_nibble(chars[0])?,
+            expand_hex_nibble(chars[1])?,
+            expand_hex_nibble(chars[2])?,
+            255,
+        ),
+        4 => (
+            expand_hex_nibble(chars[0])?,
+            expand_hex_nibble(chars[1])?,
+            expand_hex_nibble(chars[2])?,
+            expand_hex_nibble(chars[3])?,
+        ),
+        6 => (
+            u8::from_str_radix(&s[0..2], 16)?,
+            u8::from_str_radix(&s[2..4], 16)?,
+            u8::from_str_radix(&s[4..6], 16)?,
+            255,
+        ),
+        8 => (
+            u8::from_str_radix(&s[0..2], 16)?,
+            u8::from_str_radix(&s[2..4], 16)?,
+            u8::from_str_radix(&s[4..6], 16)?,
+            u8::from_str_radix(&s[6..8], 16)?,
+        ),
+        _ => bail!(
+            "Invalid hex color '#{}' (expected '#RGB', '#RGBA', '#RRGGBB' or '#RRGGBBAA')",
+            s
+        ),
+    };
+
+    Ok(tiny_skia::Color::from_rgba8(r, g, b, a))
+}
+
+/// Parses the comma-separated arguments of an `rgb(...)`/`rgba(...)` call.
+fn parse_rgb_components(inner: &str, has_alpha: bool) -> Result<tiny_skia::Color> {
+    let parts: Vec<&str> = inner.split(',').map(str::trim).collect();
+
+    if parts.len() != if has_alpha { 4 } else { 3 } {
+        bail!(
+            "Invalid 'rgb()'/'rgba()' color: expected {} components, got '{}'",
+            if has_alpha { 4 } else { 3 },
+            inner
+        );
+    }
+
+    let r: u8 = parts[0].parse()?;
+    let g: u8 = parts[1].parse()?;
+    let b: u8 = parts[2].parse()?;
+    let a = if has_alpha {
+        (parts[3].parse::<f32>()?.clamp(0.0, 1.0) * 255.0).round() as u8
+    } else {
+        255
+    };
+
+    Ok(tiny_skia::Color::from_rgba8(r, g, b, a))
+}
+
+/// Converts HSL (degrees, 0..=1, 0..=1) to RGB using the standard sextant construction.
+fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
+    let h = h.rem_euclid(360.0);
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = l - c / 2.0;
+
+    let (r1, g1, b1) = match h as u32 / 60 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    (
+        ((r1 + m) * 255.0).round().clamp(0.0, 255.0) as u8,
+        ((g1 + m) * 255.0).round().clamp(0.0, 255.0) as u8,
+        ((b1 + m) * 255.0).round().clamp(0.0, 255.0) as u8,
+    )
+}
+
+/// Parses the comma-separated arguments of an `hsl(...)`/`hsla(...)` call.
+fn parse_hsl_components(inner: &str, has_alpha: bool) -> Result<tiny_skia::Color> {
+    let parts: Vec<&str> = inner.split(',').map(str::trim).collect();
+
+    if parts.len() != if has_alpha { 4 } else { 3 } {
+        bail!(
+            "Invalid 'hsl()'/'hsla()' color: expected {} components, got '{}'",
+            if has_alpha { 4 } else { 3 },
+            inner
+        );
+    }
+
+    let h: f32 = parts[0].trim_end_matches("deg").parse()?;
+    let s: f32 = parts[1].trim_end_matches('%').parse::<f32>()? / 100.0;
+    let l: f32 = parts[2].trim_end_matches('%').parse::<f32>()? / 100.0;
+    let a = if has_alpha {
+        (parts[3].parse::<f32>()?.clamp(0.0, 1.0) * 255.0).round() as u8
+    } else {
+        255
+    };
+
+    let (r, g, b) = hsl_to_rgb(h, s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+
+    Ok(tiny_skia::Color::from_rgba8(r, g, b, a))
+}
+
+/// Parses a CSS-style color: hex (`#RGB`, `#RGBA`, `#RRGGBB`, `#RRGGBBAA`), a named color
+/// (`rebeccapurple`, `cornflowerblue`, `transparent`, ...), `rgb()`/`rgba()`, or `hsl()`/`hsla()`.
 fn parse_color(s: &str) -> Result<tiny_skia::Color> {
-    let s = s.trim_start_matches('#');
+    let s = s.trim();
 
-    let (r, g, b, a) = match s.len() {
-        6 => {
-            let r = u8::from_str_radix(&s[0..2], 16)?;
-            let g = u8::from_str_radix(&s[2..4], 16)?;
-            let b = u8::from_str_radix(&s[4..6], 16)?;
+    if let Some(hex) = s.strip_prefix('#') {
+        return parse_hex_color(hex);
+    }
+
+    if let Some(inner) = s.strip_prefix("rgba(").and_then(|v| v.strip_suffix(')')) {
+        return parse_rgb_components(inner, true);
+    }
+
+    if let Some(inner) = s.strip_prefix("rgb(").and_then(|v| v.strip_suffix(')')) {
+        return parse_rgb_components(inner, false);
+    }
+
+    if let Some(inner) = s.strip_prefix("hsla(").and_then(|v| v.strip_suffix(')')) {
+        return parse_hsl_components(inner, true);
+    }
+
+    if let Some(inner) = s.strip_prefix("hsl(").and_then(|v| v.strip_suffix(')')) {
+        return parse_hsl_components(inner, false);
+    }
+
+    if s.eq_ignore_ascii_case("transparent") {
+        return Ok(tiny_skia::Color::from_rgba8(0, 0, 0, 0));
+    }
+
+    if let Some((_, r, g, b)) = NAMED_COLORS
+        .iter()
+        .find(|(n, _, _, _)| n.eq_ignore_ascii_case(s))
+    {
+        return Ok(tiny_skia::Color::from_rgba8(*r, *g, *b, 255));
+    }
+
+    bail!(
+        "Invalid color '{}' (expected a hex color, a named color, 'rgb()'/'rgba()', or 'hsl()'/'hsla()')",
+        s
+    )
+}
+
+/// Derives the final output pixel size and per-axis render scale for an SVG whose intrinsic
+/// size is `base_width`x`base_height`. `--width`/`--height` (resolved to pixels via `--dpi` for
+/// unit-suffixed values) take precedence over `--scale`; when only one of the two is given, the
+/// other is derived to preserve the SVG's aspect ratio.
+fn resolve_size(
+    base_width: u32,
+    base_height: u32,
+    width: Option<SizeArg>,
+    height: Option<SizeArg>,
+    scale: f32,
+    dpi: f32,
+) -> (u32, u32, f32, f32) {
+    let (width, height) = if width.is_some() || height.is_some() {
+        let w_px = width.map(|v| v.to_pixels(dpi));
+        let h_px = height.map(|v| v.to_pixels(dpi));
+
+        let w = w_px.unwrap_or_else(|| {
+            h_px.map_or(base_width as f32, |v| {
+                if base_height == 0 {
+                    return 0.0;
+                }
+                let ratio = v / base_height as f32;
+                base_width as f32 * ratio
+            })
+        });
+
+        let h = h_px.unwrap_or_else(|| {
+            w_px.map_or(base_height as f32, |v| {
+                if base_width == 0 {
+                    return 0.0;
+                }
+                let ratio = v / base_width as f32;
+                base_height as f32 * ratio
+            })
+        });
+
+        (w.round() as u32, h.round() as u32)
+    } else {
+        (
+            (base_width as f32 * scale).round() as u32,
+            (base_height as f32 * scale).round() as u32,
+        )
+    };
+
+    let scale_x = width as f32 / base_width as f32;
+    let scale_y = height as f32 / base_height as f32;
 
-            (r, g, b, 255)
+    (width, height, scale_x, scale_y)
+}
+
+/// A single unit of rendering work: the SVG source bytes and the path (relative to
+/// `--output`, without extension) its generated images should be written to.
+struct Job {
+    data: Vec<u8>,
+    stem: PathBuf,
+}
+
+/// Recursively collects every `.svg` file under `dir`, preserving relative subpaths so a
+/// directory layout like `icons/sub/foo.svg` maps to `<output>/sub/foo.<ext>`. An unreadable
+/// subdirectory or file is logged and skipped rather than aborting the whole batch, so one bad
+/// entry doesn't discard every job already discovered.
+fn collect_dir_jobs(dir: &PathBuf) -> Result<Vec<Job>> {
+    let mut jobs = Vec::new();
+
+    for entry in walkdir::WalkDir::new(dir).into_iter() {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                log::warn!("Skipping an entry under '{}': {}", dir.display(), e);
+                continue;
+            }
+        };
+        let path = entry.into_path();
+
+        if !is_svg_file(&path) {
+            continue;
         }
-        8 => {
-            let r = u8::from_str_radix(&s[0..2], 16)?;
-            let g = u8::from_str_radix(&s[2..4], 16)?;
-            let b = u8::from_str_radix(&s[4..6], 16)?;
-            let a = u8::from_str_radix(&s[6..8], 16)?;
 
-            (r, g, b, a)
+        let stem = path.strip_prefix(dir).unwrap_or(&path).with_extension("");
+        let data = match std::fs::read(&path) {
+            Ok(d) => d,
+            Err(e) => {
+                log::warn!("Skipping '{}': failed to read file: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        jobs.push(Job { data, stem });
+    }
+
+    if jobs.is_empty() {
+        bail!("No '.svg' files found under '{}'.", dir.display());
+    }
+
+    Ok(jobs)
+}
+
+/// Collects every file matching the glob `pattern`, preserving the part of each match past the
+/// pattern's literal (non-wildcard) prefix as the relative stem, so `icons/**/*.svg` maps
+/// `icons/sub/foo.svg` to `<output>/sub/foo.<ext>`. As in `collect_dir_jobs`, an unreadable
+/// match is logged and skipped rather than aborting the whole batch.
+fn collect_glob_jobs(pattern: &str) -> Result<Vec<Job>> {
+    let prefix_len = pattern
+        .find(['*', '?', '[', '{'])
+        .map(|i| pattern[..i].rfind('/').map_or(0, |i| i + 1))
+        .unwrap_or(0);
+    let prefix = PathBuf::from(&pattern[..prefix_len]);
+
+    let mut jobs = Vec::new();
+
+    for entry in
+        glob::glob(pattern).with_context(|| format!("Invalid glob pattern '{}'.", pattern))?
+    {
+        let path = match entry {
+            Ok(p) => p,
+            Err(e) => {
+                log::warn!("Skipping a glob match for '{}': {}", pattern, e);
+                continue;
+            }
+        };
+
+        if !is_svg_file(&path) {
+            continue;
         }
-        _ => bail!("Invalid color format (expected '#RRGGBB' or '#RRGGBBAA')"),
-    };
 
-    Ok(tiny_skia::Color::from_rgba8(r, g, b, a))
+        let stem = path
+            .strip_prefix(&prefix)
+            .unwrap_or(&path)
+            .with_extension("");
+        let data = match std::fs::read(&path) {
+            Ok(d) => d,
+            Err(e) => {
+                log::warn!("Skipping '{}': failed to read file: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        jobs.push(Job { data, stem });
+    }
+
+    if jobs.is_empty() {
+        bail!("No '.svg' files matched '{}'.", pattern);
+    }
+
+    Ok(jobs)
 }
 
 fn pixmap_to_rgb_buffer(pixmap: &Pixmap) -> Vec<u8> {
@@ -175,6 +725,246 @@ fn pixmap_to_rgb_buffer(pixmap: &Pixmap) -> Vec<u8> {
     rgb
 }
 
+/// Per-format encoder knobs, collected once from `Config` and shared across the rayon workers.
+#[derive(Clone, Copy, Debug)]
+struct EncodeOptions {
+    jpeg_quality: u8,
+    avif_quality: u8,
+    avif_speed: u8,
+    tiff_compression: TiffCompression,
+    exr_linear: bool,
+}
+
+/// Encodes `pixmap` as `f` and writes it to `o`, applying the quality/compression knobs in
+/// `opts`. Every format but TIFF and EXR goes through the `image` crate's format-specific
+/// encoders so codec parameters are actually honored (`image::save_buffer` exposes none of them).
+fn encode(
+    f: &Format,
+    o: &PathBuf,
+    pixmap: &Pixmap,
+    width: u32,
+    height: u32,
+    opts: &EncodeOptions,
+) -> Result<()> {
+    match f {
+        Format::Tiff => return encode_tiff(o, pixmap, width, height, &opts.tiff_compression),
+        Format::Exr => return encode_exr(o, pixmap, width, height, opts.exr_linear),
+        _ => {}
+    }
+
+    let file =
+        std::fs::File::create(o).with_context(|| format!("Failed to create '{}'.", o.display()))?;
+    let w = std::io::BufWriter::new(file);
+
+    match f {
+        Format::Jpeg => {
+            let buf = pixmap_to_rgb_buffer(pixmap);
+            image::codecs::jpeg::JpegEncoder::new_with_quality(w, opts.jpeg_quality).write_image(
+                &buf,
+                width,
+                height,
+                ExtendedColorType::Rgb8,
+            )?;
+        }
+        Format::Webp => {
+            // The `image` crate's bundled encoder only supports lossless WebP; lossy encoding
+            // would require binding to libwebp directly via the separate `webp` crate.
+            image::codecs::webp::WebPEncoder::new_lossless(w).write_image(
+                pixmap.data(),
+                width,
+                height,
+                ExtendedColorType::Rgba8,
+            )?;
+        }
+        Format::Avif => {
+            image::codecs::avif::AvifEncoder::new_with_speed_quality(
+                w,
+                opts.avif_speed,
+                opts.avif_quality,
+            )
+            .write_image(pixmap.data(), width, height, ExtendedColorType::Rgba8)?;
+        }
+        Format::Tiff | Format::Exr => unreachable!("returned above"),
+        Format::Png => {
+            image::codecs::png::PngEncoder::new(w).write_image(
+                pixmap.data(),
+                width,
+                height,
+                ExtendedColorType::Rgba8,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// TIFF is written straight through the `tiff` crate instead of `image::save_buffer`, since only
+/// it exposes a choice of compression backend.
+fn encode_tiff(
+    o: &PathBuf,
+    pixmap: &Pixmap,
+    width: u32,
+    height: u32,
+    compression: &TiffCompression,
+) -> Result<()> {
+    let file =
+        std::fs::File::create(o).with_context(|| format!("Failed to create '{}'.", o.display()))?;
+    let mut encoder =
+        tiff::encoder::TiffEncoder::new(file).context("Failed to initialize TIFF encoder.")?;
+
+    match compression {
+        TiffCompression::None => encoder
+            .write_image_with_compression::<tiff::encoder::colortype::RGBA8, _>(
+                width,
+                height,
+                tiff::encoder::compression::Uncompressed,
+                pixmap.data(),
+            )?,
+        TiffCompression::Lzw => encoder
+            .write_image_with_compression::<tiff::encoder::colortype::RGBA8, _>(
+                width,
+                height,
+                tiff::encoder::compression::Lzw::default(),
+                pixmap.data(),
+            )?,
+        TiffCompression::Deflate => encoder
+            .write_image_with_compression::<tiff::encoder::colortype::RGBA8, _>(
+                width,
+                height,
+                tiff::encoder::compression::Deflate::default(),
+                pixmap.data(),
+            )?,
+        TiffCompression::Packbits => encoder
+            .write_image_with_compression::<tiff::encoder::colortype::RGBA8, _>(
+                width,
+                height,
+                tiff::encoder::compression::Packbits,
+                pixmap.data(),
+            )?,
+    };
+
+    Ok(())
+}
+
+/// Converts a single sRGB-encoded channel (`0.0..=1.0`) to linear light.
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// EXR is written straight through the `exr` crate, since it's the only format here that stores
+/// float channels rather than 8-bit ones. Channels are unpremultiplied and clamped to [0, 1], and
+/// converted from sRGB to linear light only when `linear` is set.
+fn encode_exr(o: &PathBuf, pixmap: &Pixmap, width: u32, height: u32, linear: bool) -> Result<()> {
+    let data = pixmap.data();
+
+    exr::prelude::write_rgba_file(o, width as usize, height as usize, |x, y| {
+        let i = (y * width as usize + x) * 4;
+        let a = data[i + 3] as f32 / 255.0;
+
+        // tiny-skia pixmaps are premultiplied, so we need to unpremultiply it. 8-bit rounding
+        // can push a channel fractionally above its alpha, so clamp back into [0, 1].
+        let (r, g, b) = if a > 0.0 {
+            (
+                (data[i] as f32 / 255.0 / a).clamp(0.0, 1.0),
+                (data[i + 1] as f32 / 255.0 / a).clamp(0.0, 1.0),
+                (data[i + 2] as f32 / 255.0 / a).clamp(0.0, 1.0),
+            )
+        } else {
+            (0.0, 0.0, 0.0)
+        };
+
+        if linear {
+            (srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a)
+        } else {
+            (r, g, b, a)
+        }
+    })
+    .with_context(|| format!("Failed to write '{}'.", o.display()))?;
+
+    Ok(())
+}
+
+/// Outcome of rendering and encoding a single `(file, format)` task.
+#[derive(Debug)]
+enum TaskOutcome {
+    Ok,
+    Skipped(String),
+    Error(String),
+}
+
+/// A single `(file, format)` task's outcome, collected into the end-of-run summary.
+struct TaskReport {
+    file: PathBuf,
+    format: Format,
+    outcome: TaskOutcome,
+}
+
+/// Renders `tree` into `f` at `width`x`height` and writes it under `output`. Returns
+/// `TaskOutcome::Skipped` instead of erroring when the resolved size is degenerate, since that's
+/// a configuration edge case rather than a rendering failure.
+fn render_task(
+    job: &Job,
+    f: &Format,
+    tree: &usvg::Tree,
+    width: u32,
+    height: u32,
+    scale_x: f32,
+    scale_y: f32,
+    output: &PathBuf,
+    background: Option<tiny_skia::Color>,
+    encode_opts: &EncodeOptions,
+) -> Result<TaskOutcome> {
+    if width == 0 || height == 0 {
+        return Ok(TaskOutcome::Skipped(format!(
+            "resolved size is {}x{}, nothing to render",
+            width, height
+        )));
+    }
+
+    let o = {
+        let ext = f.extension();
+        let mut o = PathBuf::from(output);
+        o.push(&job.stem);
+        o.set_extension(ext);
+        o
+    };
+
+    let transform = tiny_skia::Transform::from_scale(scale_x, scale_y);
+    let mut pixmap = tiny_skia::Pixmap::new(width, height).context("Failed to allocate pixmap.")?;
+
+    let bg_color = if let Some(v) = background {
+        v
+    } else if f.has_alpha_channel() {
+        tiny_skia::Color::from_rgba8(0, 0, 0, 0)
+    } else {
+        tiny_skia::Color::from_rgba8(255, 255, 255, 255)
+    };
+
+    pixmap.fill(bg_color);
+
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+
+    encode(f, &o, &pixmap, width, height, encode_opts)?;
+
+    Ok(TaskOutcome::Ok)
+}
+
+/// Turns a `catch_unwind` payload into a human-readable message, falling back when the panic
+/// didn't pass a `&str`/`String` (e.g. a custom payload from a dependency).
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("panicked with a non-string payload")
+    }
+}
+
 fn main() -> Result<()> {
     let env = env_logger::Env::default()
         .filter_or("V2X_LOG_LEVEL", "info")
@@ -187,6 +977,7 @@ fn main() -> Result<()> {
         || {
             vec![
                 Format::Avif,
+                Format::Exr,
                 Format::Jpeg,
                 Format::Png,
                 Format::Tiff,
@@ -217,75 +1008,67 @@ fn main() -> Result<()> {
         None
     };
 
-    let filename = match &config.input {
-        Input::Stdin => match config.filename {
-            Some(f) => OsString::from(f),
-            _ => bail!("'--filename' is required because the input comes from stdin."),
-        },
+    let encode_opts = EncodeOptions {
+        jpeg_quality: config.jpeg_quality,
+        avif_quality: config.avif_quality,
+        avif_speed: config.avif_speed,
+        tiff_compression: config.tiff_compression,
+        exr_linear: config.exr_linear,
+    };
+
+    let jobs = match &config.input {
+        Input::Stdin => {
+            let filename = match config.filename {
+                Some(f) => f,
+                _ => bail!("'--filename' is required because the input comes from stdin."),
+            };
+            let data = read_from_stdin().context("Failed to read from stdin.")?;
+
+            vec![Job {
+                data,
+                stem: PathBuf::from(filename),
+            }]
+        }
         Input::Path(p) => {
-            if !is_svg_file(&p) {
+            if !is_svg_file(p) {
                 bail!(
                     "Invalid SVG file: '{}'. Please provide a valid SVG input.",
                     p.display()
                 );
             }
 
-            match config.filename {
-                Some(f) => OsString::from(f),
-                _ => p
-                    .file_stem()
-                    .expect("filename should not be empty.")
-                    .to_owned(),
-            }
-        }
-    };
-
-    let mut opt = usvg::Options::default();
-    opt.fontdb_mut().load_system_fonts();
+            let stem = match config.filename {
+                Some(f) => PathBuf::from(f),
+                _ => PathBuf::from(p.file_stem().expect("filename should not be empty.")),
+            };
+            let data = std::fs::read(p)
+                .with_context(|| format!("Failed to read file '{}'.", p.display()))?;
 
-    let data = match &config.input {
-        Input::Stdin => read_from_stdin().context("Failed to read from stdin.")?,
-        Input::Path(p) => {
-            std::fs::read(&p).with_context(|| format!("Failed to read file '{}'.", p.display()))?
+            vec![Job { data, stem }]
         }
-    };
-    let tree = usvg::Tree::from_data(&data, &opt)?;
-
-    let size = tree.size().to_int_size();
-    let base_width = size.width();
-    let base_height = size.height();
-
-    let (width, height) = if config.width.is_some() || config.height.is_some() {
-        let w = config.width.unwrap_or_else(|| {
-            config.height.map_or_else(
-                || base_width,
-                |v| {
-                    let ratio = v as f32 / base_height as f32;
-                    (base_width as f32 * ratio) as u32
-                },
-            )
-        });
+        Input::Dir(dir) => {
+            if config.filename.is_some() {
+                bail!(
+                    "'--filename' cannot be used when input is a directory; output names are derived from each file."
+                );
+            }
 
-        let h = config.height.unwrap_or_else(|| {
-            config.width.map_or_else(
-                || base_height,
-                |v| {
-                    let ratio = v as f32 / base_width as f32;
-                    (base_height as f32 * ratio) as u32
-                },
-            )
-        });
+            collect_dir_jobs(dir)?
+        }
+        Input::Glob(pattern) => {
+            if config.filename.is_some() {
+                bail!(
+                    "'--filename' cannot be used when input is a glob pattern; output names are derived from each file."
+                );
+            }
 
-        (w, h)
-    } else {
-        (
-            (base_width as f32 * config.scale).round() as u32,
-            (base_height as f32 * config.scale).round() as u32,
-        )
+            collect_glob_jobs(pattern)?
+        }
     };
 
-    let scale_x = width as f32 / base_width as f32;
-    let scale_y = height as f32 / base_height as f32;
+    let mut opt = usvg::Options::default();
+    opt.dpi = config.dpi;
+    opt.fontdb_mut().load_system_fonts();
 
     let cores = std::thread::available_parallelism()
         .map(|n| n.get())
@@ -293,71 +1076,287 @@ fn main() -> Result<()> {
 
     log::info!("Detected {} CPU cores for parallelization.", cores);
 
-    formats.par_iter().for_each(|f| {
-        let id =
-            rayon::current_thread_index().expect("should be called from a Rayon worker thread.");
-        let start = std::time::Instant::now();
-
-        let o = {
-            let ext = f.extension();
-            let mut o = PathBuf::from(&output);
-            o.push(&filename);
-            o.set_extension(ext);
-            o
-        };
+    // `render_task` panics are caught and folded into the summary below, so silence the default
+    // hook's stderr crash dump for the duration of the parallel section.
+    let default_panic_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
 
-        let transform = tiny_skia::Transform::from_scale(scale_x, scale_y);
-        let mut pixmap = tiny_skia::Pixmap::new(width, height).expect("size should not be zero.");
+    let reports: Vec<TaskReport> = jobs
+        .par_iter()
+        .flat_map(|job| {
+            let tree = match usvg::Tree::from_data(&job.data, &opt) {
+                Ok(t) => t,
+                Err(e) => {
+                    let msg = format!("Failed to parse SVG: {}", e);
+                    return formats
+                        .iter()
+                        .map(|f| TaskReport {
+                            file: job.stem.clone(),
+                            format: f.clone(),
+                            outcome: TaskOutcome::Error(msg.clone()),
+                        })
+                        .collect::<Vec<_>>();
+                }
+            };
 
-        let bg_color = if let Some(v) = background {
-            v
-        } else if f.has_alpha_channel() {
-            tiny_skia::Color::from_rgba8(0, 0, 0, 0)
-        } else {
-            tiny_skia::Color::from_rgba8(255, 255, 255, 255)
-        };
+            let size = tree.size().to_int_size();
+            let (width, height, scale_x, scale_y) = resolve_size(
+                size.width(),
+                size.height(),
+                config.width,
+                config.height,
+                config.scale,
+                config.dpi,
+            );
 
-        pixmap.fill(bg_color);
+            if let Some(parent) = output.join(&job.stem).parent() {
+                if !parent.exists() {
+                    if let Err(e) = std::fs::create_dir_all(parent) {
+                        let msg =
+                            format!("Failed to create directory '{}': {}", parent.display(), e);
+                        return formats
+                            .iter()
+                            .map(|f| TaskReport {
+                                file: job.stem.clone(),
+                                format: f.clone(),
+                                outcome: TaskOutcome::Error(msg.clone()),
+                            })
+                            .collect::<Vec<_>>();
+                    }
+                }
+            }
 
-        resvg::render(&tree, transform, &mut pixmap.as_mut());
+            formats
+                .par_iter()
+                .map(|f| {
+                    let id = rayon::current_thread_index().unwrap_or(0);
+                    let start = std::time::Instant::now();
 
-        let res = match f {
-            Format::Jpeg => {
-                let buf = pixmap_to_rgb_buffer(&pixmap);
-                image::save_buffer(&o, &buf, width, height, ExtendedColorType::Rgb8)
-            }
-            _ => image::save_buffer(&o, pixmap.data(), width, height, ExtendedColorType::Rgba8),
-        };
+                    let outcome =
+                        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                            render_task(
+                                job,
+                                f,
+                                &tree,
+                                width,
+                                height,
+                                scale_x,
+                                scale_y,
+                                &output,
+                                background,
+                                &encode_opts,
+                            )
+                        })) {
+                            Ok(Ok(outcome)) => outcome,
+                            Ok(Err(e)) => TaskOutcome::Error(e.to_string()),
+                            Err(panic) => TaskOutcome::Error(panic_message(panic.as_ref())),
+                        };
 
-        if let Err(e) = res {
-            log::error!(
-                "[thread_id={}] Failed to generate '{}' Caused by: {}",
-                id,
-                o.file_name()
-                    .expect("path should not be terminates in `..`.")
-                    .display(),
-                e
-            );
-        } else {
-            let elapsed = if start.elapsed().as_secs() > 0 {
-                format!("{}s", start.elapsed().as_secs())
-            } else {
-                format!(
-                    "{}ms",
-                    start.elapsed().as_millis().min(u64::MAX as u128) as u64
-                )
-            };
+                    match &outcome {
+                        TaskOutcome::Ok => {
+                            let elapsed = if start.elapsed().as_secs() > 0 {
+                                format!("{}s", start.elapsed().as_secs())
+                            } else {
+                                format!(
+                                    "{}ms",
+                                    start.elapsed().as_millis().min(u64::MAX as u128) as u64
+                                )
+                            };
 
-            log::info!(
-                "[thread_id={}] Generated: '{}' in {}",
-                id,
-                o.file_name()
-                    .expect("path should not be terminates in `..`.")
-                    .display(),
-                elapsed,
-            );
+                            log::info!(
+                                "[thread_id={}] Generated: '{}.{}' in {}",
+                                id,
+                                job.stem.display(),
+                                f.extension(),
+                                elapsed,
+                            );
+                        }
+                        TaskOutcome::Skipped(reason) => {
+                            log::warn!(
+                                "[thread_id={}] Skipped '{}.{}': {}",
+                                id,
+                                job.stem.display(),
+                                f.extension(),
+                                reason,
+                            );
+                        }
+                        TaskOutcome::Error(e) => {
+                            log::error!(
+                                "[thread_id={}] Failed to generate '{}.{}' Caused by: {}",
+                                id,
+                                job.stem.display(),
+                                f.extension(),
+                                e,
+                            );
+                        }
+                    }
+
+                    TaskReport {
+                        file: job.stem.clone(),
+                        format: f.clone(),
+                        outcome,
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    std::panic::set_hook(default_panic_hook);
+
+    let total = reports.len();
+    let succeeded = reports
+        .iter()
+        .filter(|r| matches!(r.outcome, TaskOutcome::Ok))
+        .count();
+    let skipped = reports
+        .iter()
+        .filter(|r| matches!(r.outcome, TaskOutcome::Skipped(_)))
+        .count();
+    let failures: Vec<&TaskReport> = reports
+        .iter()
+        .filter(|r| matches!(r.outcome, TaskOutcome::Error(_)))
+        .collect();
+
+    log::info!(
+        "Done: {} succeeded, {} skipped, {} failed (out of {} tasks).",
+        succeeded,
+        skipped,
+        failures.len(),
+        total,
+    );
+
+    if !failures.is_empty() {
+        for r in &failures {
+            if let TaskOutcome::Error(e) = &r.outcome {
+                log::error!("  '{}.{}': {}", r.file.display(), r.format.extension(), e);
+            }
         }
-    });
+
+        bail!("{} of {} tasks failed.", failures.len(), total);
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// tiny-skia's `Color` stores premultiplied linear components as `f32`, so comparing parsed
+    /// colors exactly only works when both sides go through the same `from_rgba8` conversion.
+    fn assert_color_eq(actual: tiny_skia::Color, r: u8, g: u8, b: u8, a: u8) {
+        assert_eq!(actual, tiny_skia::Color::from_rgba8(r, g, b, a));
+    }
+
+    #[test]
+    fn parse_color_hex() {
+        assert_color_eq(parse_color("#f00").unwrap(), 255, 0, 0, 255);
+        assert_color_eq(parse_color("#f008").unwrap(), 255, 0, 0, 136);
+        assert_color_eq(parse_color("#336699").unwrap(), 0x33, 0x66, 0x99, 255);
+        assert_color_eq(parse_color("#336699cc").unwrap(), 0x33, 0x66, 0x99, 0xcc);
+        assert!(parse_color("#1234").is_ok());
+        assert!(parse_color("#12").is_err());
+    }
+
+    #[test]
+    fn parse_color_named() {
+        assert_color_eq(parse_color("rebeccapurple").unwrap(), 102, 51, 153, 255);
+        assert_color_eq(parse_color("RebeccaPurple").unwrap(), 102, 51, 153, 255);
+        assert_color_eq(parse_color("transparent").unwrap(), 0, 0, 0, 0);
+    }
+
+    #[test]
+    fn parse_color_rgb() {
+        assert_color_eq(parse_color("rgb(51, 102, 153)").unwrap(), 51, 102, 153, 255);
+        assert_color_eq(
+            parse_color("rgba(51, 102, 153, 0.5)").unwrap(),
+            51,
+            102,
+            153,
+            128,
+        );
+    }
+
+    #[test]
+    fn parse_color_hsl() {
+        // hsl(0, 100%, 50%) is pure red.
+        assert_color_eq(parse_color("hsl(0, 100%, 50%)").unwrap(), 255, 0, 0, 255);
+        assert_color_eq(
+            parse_color("hsla(0, 100%, 50%, 0.5)").unwrap(),
+            255,
+            0,
+            0,
+            128,
+        );
+    }
+
+    #[test]
+    fn parse_color_invalid() {
+        assert!(parse_color("notacolor").is_err());
+        assert!(parse_color("rgb(1, 2)").is_err());
+        assert!(parse_color("#zzz").is_err());
+    }
+
+    #[test]
+    fn hsl_to_rgb_primary_hues() {
+        assert_eq!(hsl_to_rgb(0.0, 1.0, 0.5), (255, 0, 0));
+        assert_eq!(hsl_to_rgb(120.0, 1.0, 0.5), (0, 255, 0));
+        assert_eq!(hsl_to_rgb(240.0, 1.0, 0.5), (0, 0, 255));
+    }
+
+    #[test]
+    fn hsl_to_rgb_wraps_hue() {
+        // -30 and 330 degrees are the same hue.
+        assert_eq!(hsl_to_rgb(-30.0, 1.0, 0.5), hsl_to_rgb(330.0, 1.0, 0.5));
+        assert_eq!(hsl_to_rgb(720.0, 1.0, 0.5), hsl_to_rgb(0.0, 1.0, 0.5));
+    }
+
+    #[test]
+    fn hsl_to_rgb_grayscale() {
+        assert_eq!(hsl_to_rgb(0.0, 0.0, 0.0), (0, 0, 0));
+        assert_eq!(hsl_to_rgb(0.0, 0.0, 1.0), (255, 255, 255));
+    }
+
+    #[test]
+    fn resolve_size_scale_only() {
+        let (width, height, scale_x, scale_y) = resolve_size(100, 200, None, None, 2.0, 96.0);
+        assert_eq!((width, height), (200, 400));
+        assert_eq!((scale_x, scale_y), (2.0, 2.0));
+    }
+
+    #[test]
+    fn resolve_size_width_derives_height_from_aspect_ratio() {
+        let (width, height, _, _) = resolve_size(100, 200, Some(SizeArg::Px(50)), None, 1.0, 96.0);
+        assert_eq!((width, height), (50, 100));
+    }
+
+    #[test]
+    fn resolve_size_zero_base_dimension_does_not_divide_by_zero() {
+        // A degenerate SVG with a zero base dimension: deriving the other side through the aspect
+        // ratio divides by that dimension, and must yield 0 rather than infinity.
+        let (width, height, _, _) = resolve_size(500, 0, Some(SizeArg::Px(500)), None, 1.0, 96.0);
+        assert_eq!((width, height), (500, 0));
+
+        let (width, height, _, _) = resolve_size(0, 200, None, Some(SizeArg::Px(200)), 1.0, 96.0);
+        assert_eq!((width, height), (0, 200));
+
+        let (width, height, _, _) = resolve_size(500, 0, None, Some(SizeArg::Px(100)), 1.0, 96.0);
+        assert_eq!((width, height), (0, 100));
+
+        let (width, height, _, _) = resolve_size(0, 200, Some(SizeArg::Px(100)), None, 1.0, 96.0);
+        assert_eq!((width, height), (100, 0));
+    }
+
+    #[test]
+    fn resolve_size_unit_suffixed_uses_dpi() {
+        let (width, height, _, _) = resolve_size(
+            100,
+            100,
+            Some(SizeArg::Unit(1.0, LengthUnit::In)),
+            None,
+            1.0,
+            96.0,
+        );
+        assert_eq!((width, height), (96, 96));
+    }
+}